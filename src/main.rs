@@ -1,9 +1,12 @@
+use std::fmt;
 use std::fs::OpenOptions;
-use std::io::{self, BufRead, BufReader, Write};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
 use std::os::unix::io::AsRawFd;
-use std::process::{exit, Command};
+use std::process::{exit, Child, Command, Stdio};
+use std::sync::mpsc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 // === Configuration Constants ===
 
@@ -23,6 +26,16 @@ const GRAPHICS_DEVICE_PATH: &str = "/dev/dri/card0";
 const BROWSER_EXECUTABLE_PATH: &str = "/usr/bin/ui/content_shell";
 const BROWSER_DEFAULT_URL: &str = "http://www.example.com";
 
+/// Ordered list of browser executables to try when resolving the binary
+/// Absolute paths are checked directly; bare names are resolved against each
+/// directory in `SYSTEM_PATH_VALUE`.
+const BROWSER_EXECUTABLE_CANDIDATES: &[&str] = &[
+    BROWSER_EXECUTABLE_PATH,
+    "content_shell",
+    "chromium",
+    "chrome",
+];
+
 /// Environment variables for browser and system
 const EGL_DEBUG_VALUE: &str = "1";
 const EGL_LOG_LEVEL_VALUE: &str = "debug";
@@ -34,10 +47,120 @@ const SYSTEM_PATH_VALUE: &str = "/bin:/usr/bin";
 const SYNC_INTERVAL_SECONDS: u64 = 2;
 const RETRY_DELAY_SECONDS: u64 = 2;
 
+/// Browser supervision
+/// Upper bound on the exponential restart backoff
+const BROWSER_BACKOFF_CAP_SECONDS: u64 = 60;
+/// A browser that survives at least this long is considered a stable run
+const BROWSER_RAPID_FAILURE_SECONDS: u64 = 10;
+/// Consecutive rapid failures that trip the crash-loop diagnostic
+const BROWSER_CRASH_LOOP_THRESHOLD: u32 = 3;
+
+/// Remote debugging configuration
+/// Inclusive range of TCP ports probed for a free DevTools debugging port
+const DEBUG_PORT_RANGE_START: u16 = 8000;
+const DEBUG_PORT_RANGE_END: u16 = 9000;
+/// How long to wait for the browser to announce its DevTools endpoint
+const BROWSER_READY_TIMEOUT_SECONDS: u64 = 30;
+/// How often to re-check for the DevTools endpoint while waiting
+const BROWSER_READY_POLL_INTERVAL_MILLIS: u64 = 200;
+/// File under the log mount point holding the browser's current DevTools URL
+const DEVTOOLS_URL_FILENAME: &str = "devtools.url";
+
 /// File descriptor constants
 const STDOUT_FILE_DESCRIPTOR: i32 = 1;
 const STDERR_FILE_DESCRIPTOR: i32 = 2;
 
+// === Runtime Configuration ===
+
+/// Name of the optional overlay file read from the log mount point
+const CONFIG_OVERLAY_FILE: &str = "webgate.conf";
+/// Prefix for recognised kernel command line / overlay keys
+const CONFIG_KEY_PREFIX: &str = "webgate.";
+
+/// Per-device runtime configuration
+/// Seeded from the compile-time defaults and then overridden by the kernel
+/// command line and an optional overlay file, so a single built image can be
+/// tuned per device at boot without a rebuild.
+struct Config {
+    browser_url: String,
+    log_storage_device: String,
+    browser_arguments: Vec<String>,
+    graphics_device: String,
+    terminal_device: String,
+    log_file: String,
+    debug_mode: bool,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            browser_url: BROWSER_DEFAULT_URL.to_string(),
+            log_storage_device: LOG_STORAGE_DEVICE_PATH.to_string(),
+            browser_arguments: BROWSER_ARGUMENTS.iter().map(|a| a.to_string()).collect(),
+            graphics_device: GRAPHICS_DEVICE_PATH.to_string(),
+            terminal_device: TERMINAL_DEVICE_PATH.to_string(),
+            log_file: LOG_FILE_PATH.to_string(),
+            debug_mode: DEBUG_MODE,
+        }
+    }
+}
+
+impl Config {
+    /// Apply a single `key=value` assignment, ignoring unrelated keys
+    /// The `webgate.` prefix is optional so the same keys work both on the
+    /// kernel command line and in the overlay file.
+    fn apply_assignment(&mut self, key: &str, value: &str) {
+        let key = key.strip_prefix(CONFIG_KEY_PREFIX).unwrap_or(key);
+        match key {
+            "url" => self.browser_url = value.to_string(),
+            "logdev" => self.log_storage_device = value.to_string(),
+            // Tokenized so a single overlay line can carry several arguments;
+            // on the kernel command line the value is limited to one token.
+            "args" => self.browser_arguments = parse_shell_command(value),
+            "graphics" => self.graphics_device = value.to_string(),
+            "tty" => self.terminal_device = value.to_string(),
+            "logfile" => self.log_file = value.to_string(),
+            "debug" => self.debug_mode = matches!(value, "1" | "true" | "yes"),
+            _ => {}
+        }
+    }
+
+    /// Overlay every `key=value` token from an iterator of entries
+    fn apply_entries<'a, I: IntoIterator<Item = &'a str>>(&mut self, entries: I) {
+        for entry in entries {
+            if let Some((key, value)) = entry.split_once('=') {
+                self.apply_assignment(key.trim(), value.trim());
+            }
+        }
+    }
+
+    /// Overlay a simple `key=value` file once the mount point is available
+    /// Missing or unreadable files are ignored so boot proceeds on defaults.
+    fn overlay_file(&mut self, path: &str) {
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            let entries = contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'));
+            self.apply_entries(entries);
+        }
+    }
+}
+
+/// Load runtime configuration from the kernel command line
+/// Starts from the compile-time defaults and overlays any `webgate.*` keys
+/// found in `/proc/cmdline`. The overlay file is applied separately once the
+/// log storage device has been mounted.
+fn load_config() -> Config {
+    let mut config = Config::default();
+
+    if let Ok(cmdline) = std::fs::read_to_string("/proc/cmdline") {
+        config.apply_entries(cmdline.split_whitespace());
+    }
+
+    config
+}
+
 // === Output and Utility Functions ===
 
 /// Output a line to stdout with immediate flush
@@ -61,9 +184,9 @@ fn sleep_seconds(seconds: u64) {
 
 /// Redirect stdout and stderr to the system terminal
 /// This function retries indefinitely until successful
-fn redirect_output_to_terminal() -> io::Result<()> {
+fn redirect_output_to_terminal(config: &Config) -> io::Result<()> {
     loop {
-        match OpenOptions::new().write(true).open(TERMINAL_DEVICE_PATH) {
+        match OpenOptions::new().write(true).open(&config.terminal_device) {
             Ok(terminal_file) => {
                 output_line("Redirecting output to the terminal...");
 
@@ -85,13 +208,13 @@ fn redirect_output_to_terminal() -> io::Result<()> {
 
 /// Redirect stdout and stderr to a log file
 /// This function retries indefinitely until successful
-fn redirect_output_to_log_file() -> io::Result<()> {
+fn redirect_output_to_log_file(config: &Config) -> io::Result<()> {
     loop {
         match OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(LOG_FILE_PATH)
+            .open(&config.log_file)
         {
             Ok(log_file) => {
                 output_line("Redirecting output to a log file!");
@@ -117,7 +240,7 @@ fn redirect_output_to_log_file() -> io::Result<()> {
 /// Execute a shell command with proper environment setup
 /// Commands are parsed to handle quoted arguments and are executed with
 /// full environment variables configured for browser operation
-fn execute_shell_command(command: &str) -> io::Result<()> {
+fn execute_shell_command(command: &str, config: &Config) -> io::Result<()> {
     let command_parts = parse_shell_command(command);
 
     if command_parts.is_empty() {
@@ -132,7 +255,7 @@ fn execute_shell_command(command: &str) -> io::Result<()> {
     }
 
     // Debug output to show command execution
-    if DEBUG_MODE {
+    if config.debug_mode {
         print!("$ {}", full_command_path);
         for argument in &command_parts[1..] {
             print!(" \"{}\"", argument);
@@ -146,7 +269,7 @@ fn execute_shell_command(command: &str) -> io::Result<()> {
     }
 
     // Configure environment variables for proper system and browser operation
-    configure_command_environment(&mut shell_command);
+    configure_command_environment(&mut shell_command, config);
 
     match shell_command.status() {
         Ok(exit_status) => {
@@ -167,42 +290,78 @@ fn execute_shell_command(command: &str) -> io::Result<()> {
 
 /// Configure environment variables for a command
 /// Sets up EGL, graphics, and path variables needed for browser operation
-fn configure_command_environment(command: &mut Command) {
+fn configure_command_environment(command: &mut Command, config: &Config) {
     command
         .env("EGL_DEBUG", EGL_DEBUG_VALUE)
         .env("EGL_LOG_LEVEL", EGL_LOG_LEVEL_VALUE)
-        .env("DRI_DEVICE", GRAPHICS_DEVICE_PATH)
+        .env("DRI_DEVICE", &config.graphics_device)
         .env("LIBGL_ALWAYS_SOFTWARE", LIBGL_ALWAYS_SOFTWARE_VALUE)
         .env("LD_LIBRARY_PATH", LIBRARY_PATH_VALUE)
         .env("PATH", SYSTEM_PATH_VALUE);
 }
 
 /// Parse a shell command string into individual arguments
-/// Handles quoted strings properly to preserve spaces within arguments
+/// A small tokenizer modelled on POSIX word splitting: single quotes are
+/// literal, double quotes group text while still honouring backslash escapes, a
+/// backslash emits the next character verbatim, unquoted whitespace runs
+/// separate words, and an unquoted `#` at the start of a word begins a comment
+/// that runs to the end of the input. An empty result yields no execution.
 fn parse_shell_command(command: &str) -> Vec<String> {
     let mut arguments = Vec::new();
     let mut current_argument = String::new();
-    let mut inside_quotes = false;
-    let mut character_iterator = command.chars().peekable();
+    // Tracks whether the current word exists, so quoting can produce an empty
+    // argument (`''`) that whitespace splitting never would.
+    let mut has_argument = false;
+    let mut character_iterator = command.chars();
 
     while let Some(character) = character_iterator.next() {
         match character {
+            '\\' => {
+                if let Some(escaped) = character_iterator.next() {
+                    current_argument.push(escaped);
+                    has_argument = true;
+                }
+            }
+            '\'' => {
+                has_argument = true;
+                for literal in character_iterator.by_ref() {
+                    if literal == '\'' {
+                        break;
+                    }
+                    current_argument.push(literal);
+                }
+            }
             '"' => {
-                inside_quotes = !inside_quotes;
+                has_argument = true;
+                while let Some(quoted) = character_iterator.next() {
+                    match quoted {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = character_iterator.next() {
+                                current_argument.push(escaped);
+                            }
+                        }
+                        _ => current_argument.push(quoted),
+                    }
+                }
             }
-            ' ' if !inside_quotes => {
-                if !current_argument.is_empty() {
-                    arguments.push(current_argument.clone());
-                    current_argument.clear();
+            // An unquoted '#' only starts a comment at a word boundary; inside a
+            // word (e.g. a colour literal) it is an ordinary character.
+            '#' if !has_argument => break,
+            character if character.is_whitespace() => {
+                if has_argument {
+                    arguments.push(std::mem::take(&mut current_argument));
+                    has_argument = false;
                 }
             }
             _ => {
                 current_argument.push(character);
+                has_argument = true;
             }
         }
     }
 
-    if !current_argument.is_empty() {
+    if has_argument {
         arguments.push(current_argument);
     }
 
@@ -213,13 +372,13 @@ fn parse_shell_command(command: &str) -> Vec<String> {
 
 /// Start a background process that syncs filesystem data periodically
 /// This helps prevent data loss in case of unexpected shutdown
-fn start_sync_process() -> io::Result<()> {
+fn start_sync_process(config: &Config) -> io::Result<()> {
     match unsafe { libc::fork() } {
         0 => {
             // Child process - run sync every few seconds
             loop {
                 sleep_seconds(SYNC_INTERVAL_SECONDS);
-                let _ = execute_shell_command("sync");
+                let _ = execute_shell_command("sync", config);
             }
         }
         -1 => {
@@ -265,7 +424,7 @@ fn create_symbolic_links() -> io::Result<()> {
 
 /// Mount essential system filesystems
 /// Sets up proc, dev, and sys filesystems needed for system operation
-fn mount_filesystems() -> io::Result<()> {
+fn mount_filesystems(config: &Config) -> io::Result<()> {
     let mount_commands = [
         "mkdir -p /proc",
         "mkdir -p /dev",
@@ -275,7 +434,7 @@ fn mount_filesystems() -> io::Result<()> {
     ];
 
     for mount_command in &mount_commands {
-        execute_shell_command(mount_command)?;
+        execute_shell_command(mount_command, config)?;
     }
 
     Ok(())
@@ -283,7 +442,7 @@ fn mount_filesystems() -> io::Result<()> {
 
 /// Setup temporary filesystems for shared memory and temporary files
 /// Configures appropriate permissions and ownership for browser operation
-fn setup_temporary_filesystems() -> io::Result<()> {
+fn setup_temporary_filesystems(config: &Config) -> io::Result<()> {
     let temporary_filesystem_commands = [
         "mkdir -p /dev/shm",
         "mount -t tmpfs -o nosuid,nodev,uid=1000,gid=1000,mode=0777 shmfs /dev/shm",
@@ -291,7 +450,7 @@ fn setup_temporary_filesystems() -> io::Result<()> {
     ];
 
     for temp_command in &temporary_filesystem_commands {
-        execute_shell_command(temp_command)?;
+        execute_shell_command(temp_command, config)?;
     }
 
     Ok(())
@@ -300,12 +459,12 @@ fn setup_temporary_filesystems() -> io::Result<()> {
 /// Mount persistent log storage device
 /// Waits for the log storage device to become available and mounts it
 /// This provides persistent storage for log files
-fn mount_log_storage() -> io::Result<()> {
+fn mount_log_storage(config: &Config) -> io::Result<()> {
     output_line("Waiting for log storage device to become available...");
-    
+
     // Wait for the log storage device to become available
     loop {
-        if std::path::Path::new(LOG_STORAGE_DEVICE_PATH).exists() {
+        if std::path::Path::new(&config.log_storage_device).exists() {
             output_line("Log storage device detected, proceeding with mount...");
             break;
         }
@@ -314,12 +473,12 @@ fn mount_log_storage() -> io::Result<()> {
     }
 
     let storage_commands = [
-        &format!("mkdir -p {}", MOUNT_POINT_PATH),
-        &format!("mount {} {}", LOG_STORAGE_DEVICE_PATH, MOUNT_POINT_PATH),
+        format!("mkdir -p {}", MOUNT_POINT_PATH),
+        format!("mount {} {}", config.log_storage_device, MOUNT_POINT_PATH),
     ];
 
     for storage_command in &storage_commands {
-        execute_shell_command(storage_command)?;
+        execute_shell_command(storage_command, config)?;
     }
 
     output_line("Log storage device mounted successfully");
@@ -337,33 +496,780 @@ const BROWSER_ARGUMENTS: &[&str] = &[
     "--content-shell-hide-toolbar", // We do not want the toolbar
 ];
 
+/// Errors that can occur while bringing the browser up with remote debugging
+#[derive(Debug)]
+enum BrowserLaunchError {
+    /// No port in the probed range could be bound
+    NoFreePort,
+    /// The browser reported the requested debugging port was already taken
+    DebugPortInUse(u16),
+    /// The browser never announced its DevTools endpoint within the timeout
+    PortOpenTimeout,
+    /// The browser process could not be spawned at all
+    Spawn(io::Error),
+    /// None of the candidate browser executables could be resolved
+    NoExecutable(Vec<String>),
+}
+
+impl fmt::Display for BrowserLaunchError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BrowserLaunchError::NoFreePort => write!(
+                formatter,
+                "no free debugging port in range {}-{}",
+                DEBUG_PORT_RANGE_START, DEBUG_PORT_RANGE_END
+            ),
+            BrowserLaunchError::DebugPortInUse(port) => {
+                write!(formatter, "debugging port {} is already in use", port)
+            }
+            BrowserLaunchError::PortOpenTimeout => write!(
+                formatter,
+                "browser did not report a DevTools endpoint within {} seconds",
+                BROWSER_READY_TIMEOUT_SECONDS
+            ),
+            BrowserLaunchError::Spawn(error) => {
+                write!(formatter, "failed to spawn browser: {}", error)
+            }
+            BrowserLaunchError::NoExecutable(tried) => write!(
+                formatter,
+                "no usable browser executable found; tried: {}",
+                tried.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BrowserLaunchError {}
+
+impl From<BrowserLaunchError> for io::Error {
+    fn from(error: BrowserLaunchError) -> io::Error {
+        io::Error::other(error.to_string())
+    }
+}
+
+/// A running browser together with its live DevTools endpoint
+/// The child handle is retained so the caller can wait on or supervise it.
+struct BrowserProcess {
+    child: Child,
+    devtools_url: String,
+}
+
+/// Probe the debugging port range and return the first port we can bind
+/// Binding a `TcpListener` and immediately dropping it leaves the port free
+/// for the browser to claim moments later.
+fn find_free_debug_port() -> Result<u16, BrowserLaunchError> {
+    for port in DEBUG_PORT_RANGE_START..=DEBUG_PORT_RANGE_END {
+        if TcpListener::bind(("127.0.0.1", port)).is_ok() {
+            return Ok(port);
+        }
+    }
+
+    Err(BrowserLaunchError::NoFreePort)
+}
+
+/// Extract the DevTools WebSocket URL from a line of browser stderr
+/// Returns the `ws://` endpoint if the line matches the readiness banner.
+fn parse_devtools_url(line: &str) -> Option<String> {
+    const BANNER: &str = "DevTools listening on ";
+    let start = line.find(BANNER)? + BANNER.len();
+    let url = line[start..].trim();
+
+    if url.starts_with("ws://") && url.contains("/devtools/browser/") {
+        Some(url.to_string())
+    } else {
+        None
+    }
+}
+
+/// Check whether a path refers to a regular file with an execute bit set
+fn is_executable(path: &str) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    match std::fs::metadata(path) {
+        Ok(metadata) => metadata.is_file() && metadata.permissions().mode() & 0o111 != 0,
+        Err(_) => false,
+    }
+}
+
+/// Resolve the browser executable from the ordered candidate list
+/// Absolute candidates are checked as-is; bare names are resolved against each
+/// directory in `SYSTEM_PATH_VALUE`. Returns the first executable path and
+/// logs which candidate was chosen, or a fatal error listing every path tried.
+fn resolve_browser_executable() -> Result<String, BrowserLaunchError> {
+    let mut tried = Vec::new();
+
+    for candidate in BROWSER_EXECUTABLE_CANDIDATES {
+        if candidate.contains('/') {
+            tried.push(candidate.to_string());
+            if is_executable(candidate) {
+                output_line(&format!("Resolved browser executable: {}", candidate));
+                return Ok(candidate.to_string());
+            }
+        } else {
+            for directory in SYSTEM_PATH_VALUE.split(':') {
+                let path = format!("{}/{}", directory, candidate);
+                if is_executable(&path) {
+                    output_line(&format!("Resolved browser executable: {}", path));
+                    return Ok(path);
+                }
+                tried.push(path);
+            }
+        }
+    }
+
+    Err(BrowserLaunchError::NoExecutable(tried))
+}
+
 /// Start the web browser with robust configuration
-/// Attempts to launch the browser with comprehensive arguments for embedded systems
-fn start_browser() -> io::Result<()> {
+/// Spawns content_shell with a dedicated remote debugging port and waits until
+/// it announces its DevTools endpoint on stderr, giving the caller a real
+/// readiness signal instead of a blind launch.
+fn start_browser(config: &Config) -> Result<BrowserProcess, BrowserLaunchError> {
     output_line("Starting web browser...");
 
-    // Build the complete browser command
-    let mut browser_command = String::from(BROWSER_EXECUTABLE_PATH);
+    let executable = resolve_browser_executable()?;
+
+    let debug_port = find_free_debug_port()?;
+    output_line(&format!("Using remote debugging port {}", debug_port));
+
+    let mut browser_command = Command::new(&executable);
+    browser_command.args(&config.browser_arguments);
+    browser_command.arg(format!("--remote-debugging-port={}", debug_port));
+    browser_command.arg(&config.browser_url);
+
+    // Capture stderr so we can watch for the DevTools banner rather than letting
+    // it inherit the redirected log file descriptors.
+    browser_command.stderr(Stdio::piped());
+    configure_command_environment(&mut browser_command, config);
+
+    let mut child = browser_command.spawn().map_err(BrowserLaunchError::Spawn)?;
+    let child_stderr = child
+        .stderr
+        .take()
+        .expect("stderr was configured as piped");
+
+    // Read stderr on a dedicated thread so the readiness wait can enforce a
+    // timeout even though line reads themselves block. The thread keeps draining
+    // the pipe for the child's whole lifetime: while the readiness wait is
+    // listening it forwards lines over the channel, and once that receiver goes
+    // away it logs the remaining lines directly so content_shell never stalls on
+    // EPIPE during a crash-loop.
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let reader = BufReader::new(child_stderr);
+        let mut forwarding = true;
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if forwarding {
+                if let Err(mpsc::SendError(line)) = sender.send(line) {
+                    // The readiness wait has finished; keep draining and logging.
+                    forwarding = false;
+                    output_line(&line);
+                }
+            } else {
+                output_line(&line);
+            }
+        }
+    });
+
+    let devtools_url = match wait_for_devtools_url(&receiver, debug_port, config) {
+        Ok(url) => url,
+        Err(error) => {
+            // Don't orphan the spawned process on a post-spawn failure: kill and
+            // reap it before surfacing the error, otherwise the supervisor's retry
+            // loop would pile up content_shell instances on the kiosk.
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(error);
+        }
+    };
+    output_line(&format!("Browser ready, DevTools at {}", devtools_url));
+
+    Ok(BrowserProcess {
+        child,
+        devtools_url,
+    })
+}
+
+/// Wait for the DevTools endpoint to appear on the browser's stderr
+/// Polls the stderr channel with short timeouts until the readiness banner
+/// arrives, the browser reports the port is taken, or the overall deadline
+/// elapses.
+fn wait_for_devtools_url(
+    receiver: &mpsc::Receiver<String>,
+    debug_port: u16,
+    config: &Config,
+) -> Result<String, BrowserLaunchError> {
+    let deadline = Instant::now() + Duration::from_secs(BROWSER_READY_TIMEOUT_SECONDS);
+    let poll_interval = Duration::from_millis(BROWSER_READY_POLL_INTERVAL_MILLIS);
+
+    while Instant::now() < deadline {
+        match receiver.recv_timeout(poll_interval) {
+            Ok(line) => {
+                if config.debug_mode {
+                    output_line(&line);
+                }
+                if let Some(url) = parse_devtools_url(&line) {
+                    return Ok(url);
+                }
+                if line.contains("Address already in use") {
+                    return Err(BrowserLaunchError::DebugPortInUse(debug_port));
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    Err(BrowserLaunchError::PortOpenTimeout)
+}
+
+/// Keep the browser alive for the lifetime of the device
+/// Owns the browser process, waits on it, and relaunches on unexpected exit
+/// with an exponential backoff, mirroring how a service manager keeps a unit
+/// running. A browser that dies soon after launch several times in a row is
+/// flagged as crash-looping so operators can distinguish a config problem from
+/// a transient crash. This runs in the foreground of the init sequence and does
+/// not return.
+fn supervise_browser(config: &Config, mut profile: BootProfile) -> ! {
+    let mut backoff_seconds = RETRY_DELAY_SECONDS;
+    let mut consecutive_rapid_failures: u32 = 0;
+    // Boot-relative offset of the browser launch step, fixed before the loop so
+    // the profile reflects the first launch rather than any later restart.
+    let browser_step_offset_ms = profile.boot_start.elapsed().as_millis();
+
+    loop {
+        let launched_at = Instant::now();
+
+        match start_browser(config) {
+            Ok(mut browser) => {
+                output_line(&format!(
+                    "Browser running (pid {}) at {}",
+                    browser.child.id(),
+                    browser.devtools_url
+                ));
+
+                // Publish the live endpoint so the control channel reconnects to
+                // the current browser across restarts.
+                let url_path = format!("{}/{}", MOUNT_POINT_PATH, DEVTOOLS_URL_FILENAME);
+                if let Err(error) = std::fs::write(&url_path, &browser.devtools_url) {
+                    output_line(&format!("Failed to publish DevTools URL: {}", error));
+                }
+
+                // The first successful launch is the time-to-browser-ready; flush
+                // the boot profile to log storage exactly once.
+                if !profile.persisted {
+                    profile.record(
+                        "web-browser",
+                        browser_step_offset_ms,
+                        launched_at.elapsed().as_millis(),
+                    );
+                    let total_ms = profile.boot_start.elapsed().as_millis();
+                    profile.persist(total_ms);
+                    profile.persisted = true;
+                }
+                match browser.child.wait() {
+                    Ok(exit_status) => output_line(&format!(
+                        "Browser exited with code {:?} after {:?}",
+                        exit_status.code(),
+                        launched_at.elapsed()
+                    )),
+                    Err(wait_error) => {
+                        output_line(&format!("Failed to wait on browser process: {}", wait_error))
+                    }
+                }
+            }
+            Err(launch_error) => {
+                output_line(&format!("Browser launch failed: {}", launch_error));
+            }
+        }
 
-    // Add all browser arguments
-    for argument in BROWSER_ARGUMENTS {
-        browser_command.push(' ');
-        browser_command.push_str(argument);
+        if launched_at.elapsed() >= Duration::from_secs(BROWSER_RAPID_FAILURE_SECONDS) {
+            // A genuinely stable run resets the backoff schedule.
+            consecutive_rapid_failures = 0;
+            backoff_seconds = RETRY_DELAY_SECONDS;
+        } else {
+            consecutive_rapid_failures += 1;
+            if consecutive_rapid_failures >= BROWSER_CRASH_LOOP_THRESHOLD {
+                output_line(&format!(
+                    "WARNING: browser has crash-looped {} times in a row; \
+                     check the configuration (URL, binary, graphics device)",
+                    consecutive_rapid_failures
+                ));
+            }
+        }
+
+        output_line(&format!("Restarting browser in {} seconds...", backoff_seconds));
+        sleep_seconds(backoff_seconds);
+        backoff_seconds = (backoff_seconds * 2).min(BROWSER_BACKOFF_CAP_SECONDS);
     }
+}
+
+// === Boot Profiling ===
 
-    // Add the target URL
-    browser_command.push(' ');
-    browser_command.push_str(BROWSER_DEFAULT_URL);
+/// Timing record for a single initialization step
+struct StepTiming {
+    name: String,
+    start_offset_ms: u128,
+    duration_ms: u128,
+}
 
-    // Execute the browser command
-    execute_shell_command(&browser_command)
+/// Boot-chart style instrumentation for the initialization sequence
+/// Captures when each step started relative to boot and how long it took, then
+/// persists the collected profile to log storage so operators can see which
+/// phase dominates boot latency on real hardware.
+struct BootProfile {
+    boot_start: Instant,
+    steps: Vec<StepTiming>,
+    persisted: bool,
+}
+
+impl BootProfile {
+    /// Begin profiling from the current instant
+    fn start() -> BootProfile {
+        BootProfile {
+            boot_start: Instant::now(),
+            steps: Vec::new(),
+            persisted: false,
+        }
+    }
+
+    /// Time a step, recording its boot-relative offset and duration
+    /// The step body's return value is passed through so callers keep their
+    /// usual `?` error propagation.
+    fn step<T>(&mut self, name: &str, body: impl FnOnce() -> T) -> T {
+        let start_offset_ms = self.boot_start.elapsed().as_millis();
+        let step_start = Instant::now();
+        let result = body();
+        self.record(name, start_offset_ms, step_start.elapsed().as_millis());
+        result
+    }
+
+    /// Append a pre-measured timing record
+    fn record(&mut self, name: &str, start_offset_ms: u128, duration_ms: u128) {
+        self.steps.push(StepTiming {
+            name: name.to_string(),
+            start_offset_ms,
+            duration_ms,
+        });
+    }
+
+    /// Write the collected profile to log storage as CSV and an SVG timeline
+    /// Failures are logged rather than propagated; a missing boot chart must
+    /// never hold up the browser coming online.
+    fn persist(&self, total_ms: u128) {
+        let csv_path = format!("{}/boot-profile.csv", MOUNT_POINT_PATH);
+        if let Err(error) = self.write_csv(&csv_path, total_ms) {
+            output_line(&format!("Failed to write boot profile CSV: {}", error));
+        }
+
+        let svg_path = format!("{}/boot-profile.svg", MOUNT_POINT_PATH);
+        if let Err(error) = self.write_svg(&svg_path, total_ms) {
+            output_line(&format!("Failed to write boot profile SVG: {}", error));
+        }
+    }
+
+    /// Write the profile as a CSV table with a trailing total row
+    fn write_csv(&self, path: &str, total_ms: u128) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+
+        writeln!(file, "step,start_offset_ms,duration_ms")?;
+        for step in &self.steps {
+            writeln!(
+                file,
+                "{},{},{}",
+                step.name, step.start_offset_ms, step.duration_ms
+            )?;
+        }
+        writeln!(file, "total,0,{}", total_ms)?;
+
+        Ok(())
+    }
+
+    /// Write the profile as a minimal, self-contained SVG timeline bar chart
+    fn write_svg(&self, path: &str, total_ms: u128) -> io::Result<()> {
+        const BAR_HEIGHT: u128 = 20;
+        const BAR_GAP: u128 = 6;
+        const CHART_WIDTH: u128 = 800;
+        const LABEL_WIDTH: u128 = 180;
+
+        let timeline_width = CHART_WIDTH - LABEL_WIDTH;
+        let scale = if total_ms == 0 { 0.0 } else { timeline_width as f64 / total_ms as f64 };
+        let height = (self.steps.len() as u128 + 1) * (BAR_HEIGHT + BAR_GAP) + BAR_GAP;
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+
+        writeln!(
+            file,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">",
+            CHART_WIDTH, height
+        )?;
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let y = BAR_GAP + index as u128 * (BAR_HEIGHT + BAR_GAP);
+            let x = LABEL_WIDTH + (step.start_offset_ms as f64 * scale) as u128;
+            let width = ((step.duration_ms as f64 * scale) as u128).max(1);
+
+            writeln!(
+                file,
+                "  <text x=\"4\" y=\"{}\" font-family=\"monospace\" font-size=\"12\">{}</text>",
+                y + BAR_HEIGHT - 6,
+                step.name
+            )?;
+            writeln!(
+                file,
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#4c78a8\" />",
+                x, y, width, BAR_HEIGHT
+            )?;
+            writeln!(
+                file,
+                "  <text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"11\">{} ms</text>",
+                x + width + 4,
+                y + BAR_HEIGHT - 6,
+                step.duration_ms
+            )?;
+        }
+
+        writeln!(
+            file,
+            "  <text x=\"4\" y=\"{}\" font-family=\"monospace\" font-size=\"12\">time-to-browser-ready: {} ms</text>",
+            height - BAR_GAP,
+            total_ms
+        )?;
+        writeln!(file, "</svg>")?;
+
+        Ok(())
+    }
+}
+
+// === DevTools Control Channel ===
+
+/// Standard base64 alphabet, used to decode screenshot payloads
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Decode a standard base64 string into raw bytes
+/// Whitespace and padding are ignored; returns `None` if a character lies
+/// outside the base64 alphabet. Trailing bits too few to form a byte are dropped.
+fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut output = Vec::new();
+
+    for byte in input.bytes() {
+        if byte == b'=' || byte.is_ascii_whitespace() {
+            continue;
+        }
+        let value = BASE64_ALPHABET.iter().position(|&c| c == byte)? as u32;
+        buffer = (buffer << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+/// Minimal Chrome DevTools protocol client over a raw WebSocket
+/// Speaks just enough of the JSON-RPC protocol to steer the kiosk: each request
+/// is sent as `{"id":N,"method":...,"params":...}` and the matching reply is
+/// awaited by id on the same socket.
+struct DevToolsClient {
+    stream: TcpStream,
+    next_id: u64,
+}
+
+impl DevToolsClient {
+    /// Connect to a `ws://host:port/path` DevTools endpoint
+    /// Performs the HTTP upgrade handshake and leaves the socket ready for
+    /// framed JSON-RPC traffic.
+    fn connect(url: &str) -> io::Result<DevToolsClient> {
+        let rest = url.strip_prefix("ws://").ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "DevTools URL must start with ws://")
+        })?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+
+        let mut stream = TcpStream::connect(authority)?;
+
+        // RFC 6455 handshake; a fixed key is acceptable since we never validate
+        // the server's Sec-WebSocket-Accept response.
+        let request = format!(
+            "GET {} HTTP/1.1\r\n\
+             Host: {}\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+             Sec-WebSocket-Version: 13\r\n\r\n",
+            path, authority
+        );
+        stream.write_all(request.as_bytes())?;
+
+        read_http_headers(&mut stream)?;
+
+        Ok(DevToolsClient {
+            stream,
+            next_id: 1,
+        })
+    }
+
+    /// Send a JSON-RPC request and wait for the reply with the matching id
+    /// `params` must be a JSON object literal (e.g. `{"url":"..."}`).
+    fn request(&mut self, method: &str, params: &str) -> io::Result<String> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let payload = format!(
+            "{{\"id\":{},\"method\":\"{}\",\"params\":{}}}",
+            id, method, params
+        );
+        self.send_frame(payload.as_bytes())?;
+
+        // Skip event frames until the response carrying our id arrives.
+        let needle = format!("\"id\":{}", id);
+        loop {
+            let message = self.read_message()?;
+            let text = String::from_utf8_lossy(&message).into_owned();
+            if text.contains(&needle) {
+                // A JSON-RPC reply with an `error` member is a failure, not a
+                // success; surface it instead of returning the frame verbatim.
+                if text.contains("\"error\":") {
+                    let detail = extract_json_string(&text, "message")
+                        .unwrap_or_else(|| text.clone());
+                    return Err(io::Error::other(format!(
+                        "DevTools returned an error: {}",
+                        detail
+                    )));
+                }
+                return Ok(text);
+            }
+        }
+    }
+
+    /// Write a masked client text frame
+    fn send_frame(&mut self, payload: &[u8]) -> io::Result<()> {
+        let mut frame = vec![0x81]; // FIN + text opcode
+        let length = payload.len();
+        if length < 126 {
+            frame.push(0x80 | length as u8);
+        } else if length < 65536 {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(length as u16).to_be_bytes());
+        } else {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&(length as u64).to_be_bytes());
+        }
+
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        frame.extend_from_slice(&mask);
+        for (index, byte) in payload.iter().enumerate() {
+            frame.push(byte ^ mask[index % 4]);
+        }
+
+        self.stream.write_all(&frame)
+    }
+
+    /// Read a complete message, reassembling any continuation frames
+    /// A large `Page.captureScreenshot` reply is commonly split into FIN=0
+    /// fragments, so accumulate payloads until a frame with the FIN bit set.
+    fn read_message(&mut self) -> io::Result<Vec<u8>> {
+        let mut message = Vec::new();
+        loop {
+            let (is_final, payload) = self.read_frame()?;
+            message.extend_from_slice(&payload);
+            if is_final {
+                return Ok(message);
+            }
+        }
+    }
+
+    /// Read a single server frame, returning its FIN flag and payload
+    fn read_frame(&mut self) -> io::Result<(bool, Vec<u8>)> {
+        let mut header = [0u8; 2];
+        self.stream.read_exact(&mut header)?;
+        let is_final = header[0] & 0x80 != 0;
+
+        let mut length = (header[1] & 0x7f) as usize;
+        if length == 126 {
+            let mut extended = [0u8; 2];
+            self.stream.read_exact(&mut extended)?;
+            length = u16::from_be_bytes(extended) as usize;
+        } else if length == 127 {
+            let mut extended = [0u8; 8];
+            self.stream.read_exact(&mut extended)?;
+            length = u64::from_be_bytes(extended) as usize;
+        }
+
+        let mask = if header[1] & 0x80 != 0 {
+            let mut key = [0u8; 4];
+            self.stream.read_exact(&mut key)?;
+            Some(key)
+        } else {
+            None
+        };
+
+        let mut payload = vec![0u8; length];
+        self.stream.read_exact(&mut payload)?;
+        if let Some(key) = mask {
+            for (index, byte) in payload.iter_mut().enumerate() {
+                *byte ^= key[index % 4];
+            }
+        }
+
+        Ok((is_final, payload))
+    }
+
+    /// Navigate the open page to a new URL
+    fn navigate(&mut self, url: &str) -> io::Result<()> {
+        let params = format!("{{\"url\":\"{}\"}}", url);
+        self.request("Page.navigate", &params)?;
+        Ok(())
+    }
+
+    /// Capture a screenshot and write the decoded PNG under the log mount point
+    fn capture_screenshot(&mut self, name: &str) -> io::Result<()> {
+        let response = self.request("Page.captureScreenshot", "{}")?;
+
+        let data = extract_json_string(&response, "data").ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "screenshot response had no data field")
+        })?;
+        let png = base64_decode(&data).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "screenshot data was not valid base64")
+        })?;
+
+        let path = format!("{}/{}.png", MOUNT_POINT_PATH, name);
+        std::fs::write(&path, png)?;
+        output_line(&format!("Wrote screenshot to {}", path));
+
+        Ok(())
+    }
+}
+
+/// Read HTTP response headers from a stream up to the blank separator line
+/// Reads one byte at a time so no WebSocket frame bytes are consumed.
+fn read_http_headers(stream: &mut TcpStream) -> io::Result<()> {
+    let mut recent = Vec::new();
+    let mut byte = [0u8; 1];
+    loop {
+        stream.read_exact(&mut byte)?;
+        recent.push(byte[0]);
+        if recent.ends_with(b"\r\n\r\n") {
+            return Ok(());
+        }
+    }
+}
+
+/// Extract the string value of the first occurrence of a JSON key, at any
+/// nesting depth, via a simple scan
+/// Handles backslash escapes in the value; good enough for the small, flat
+/// DevTools responses we consume.
+fn extract_json_string(json: &str, key: &str) -> Option<String> {
+    let pattern = format!("\"{}\":\"", key);
+    let start = json.find(&pattern)? + pattern.len();
+
+    let mut value = String::new();
+    let mut characters = json[start..].chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' => {
+                if let Some(escaped) = characters.next() {
+                    value.push(escaped);
+                }
+            }
+            '"' => return Some(value),
+            _ => value.push(character),
+        }
+    }
+
+    None
+}
+
+/// Resolve a page-target WebSocket endpoint from the browser endpoint
+/// `Page.*` methods are not served on the browser-level endpoint, so query the
+/// HTTP `/json` listing for the first `ws://…/devtools/page/<id>` target.
+fn resolve_page_target(browser_url: &str) -> io::Result<String> {
+    let authority = browser_url
+        .strip_prefix("ws://")
+        .and_then(|rest| rest.split('/').next())
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "DevTools URL must start with ws://")
+        })?;
+
+    let mut stream = TcpStream::connect(authority)?;
+    let request = format!(
+        "GET /json HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        authority
+    );
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    // Walk every listed target, skipping non-page ones (workers, the browser).
+    let key = "\"webSocketDebuggerUrl\":\"";
+    let mut remaining = response.as_str();
+    while let Some(index) = remaining.find(key) {
+        remaining = &remaining[index..];
+        if let Some(url) = extract_json_string(remaining, "webSocketDebuggerUrl") {
+            if url.contains("/devtools/page/") {
+                return Ok(url);
+            }
+        }
+        remaining = &remaining[key.len()..];
+    }
+
+    Err(io::Error::new(io::ErrorKind::NotFound, "no page target available"))
+}
+
+/// Open a DevTools control connection using the URL published by the supervisor
+/// The supervisor writes the live browser endpoint under the log mount point on
+/// every launch; this resolves a page target from it so `Page.*` methods reach
+/// an actual page rather than the browser endpoint.
+fn control_connect() -> io::Result<DevToolsClient> {
+    let url_path = format!("{}/{}", MOUNT_POINT_PATH, DEVTOOLS_URL_FILENAME);
+    let browser_url = std::fs::read_to_string(&url_path)?;
+    let page_url = resolve_page_target(browser_url.trim())?;
+    DevToolsClient::connect(&page_url)
 }
 
 // === Interactive Shell ===
 
+/// Steer the kiosk to a new URL over the DevTools control channel
+fn handle_goto_command(url: &str) {
+    match control_connect().and_then(|mut client| client.navigate(url)) {
+        Ok(()) => output_line(&format!("Navigated to {}", url)),
+        Err(error) => output_line(&format!("goto failed: {}", error)),
+    }
+}
+
+/// Capture a screenshot over the DevTools control channel for remote diagnostics
+fn handle_shot_command(name: &str) {
+    match control_connect().and_then(|mut client| client.capture_screenshot(name)) {
+        Ok(()) => {}
+        Err(error) => output_line(&format!("shot failed: {}", error)),
+    }
+}
+
 /// Provide an interactive shell for user commands
-/// Reads commands from the console and executes them using the shell command processor
-fn interactive_shell() -> io::Result<()> {
+/// Reads commands from the console and executes them using the shell command
+/// processor. Alongside the shell passthrough it recognises `goto <url>` and
+/// `shot <name>`, which drive the browser over the DevTools control channel.
+fn interactive_shell(config: &Config) -> io::Result<()> {
     output_line("Starting interactive shell. Type commands or Ctrl+C to exit.");
 
     loop {
@@ -389,7 +1295,13 @@ fn interactive_shell() -> io::Result<()> {
                         output_line("Exiting interactive shell...");
                         break;
                     }
-                    let _ = execute_shell_command(trimmed_command);
+                    if let Some(url) = trimmed_command.strip_prefix("goto ") {
+                        handle_goto_command(url.trim());
+                    } else if let Some(name) = trimmed_command.strip_prefix("shot ") {
+                        handle_shot_command(name.trim());
+                    } else {
+                        let _ = execute_shell_command(trimmed_command, config);
+                    }
                 }
             }
             Err(_) => {
@@ -410,39 +1322,66 @@ fn run_initialization() -> io::Result<()> {
     output_line("Starting webgate initializer...");
     output_line("Initializing system components...");
 
+    // Load per-device configuration from the kernel command line; the overlay
+    // file is applied later, once the log storage device is mounted.
+    let mut config = load_config();
+
+    // Instrument the boot sequence so the per-step timing profile can be written
+    // to log storage once the mount point is available.
+    let mut profile = BootProfile::start();
+
     output_line("[1/9]: Setting up symbolic links");
-    create_symbolic_links()?;
+    profile.step("symbolic-links", create_symbolic_links)?;
 
     output_line("[2/9]: Mounting basic filesystems");
-    mount_filesystems()?;
+    profile.step("basic-filesystems", || mount_filesystems(&config))?;
 
     output_line("[3/9]: Starting background sync process");
-    start_sync_process()?;
+    profile.step("sync-process", || start_sync_process(&config))?;
 
     output_line("[4/9]: Configuring output redirection");
-    redirect_output_to_terminal()?;
+    profile.step("output-redirection", || redirect_output_to_terminal(&config))?;
 
     output_line("[5/9]: Setting up temporary filesystems");
-    setup_temporary_filesystems()?;
+    profile.step("temporary-filesystems", || setup_temporary_filesystems(&config))?;
 
     output_line("[6/9]: Mounting log storage device");
-    if DEBUG_MODE {
-        mount_log_storage()?;
-    }
+    // Mount and overlay regardless of debug_mode: an on-disk webgate.conf must
+    // be honored even when it is the thing turning debug off.
+    profile.step("log-storage", || mount_log_storage(&config))?;
+    config.overlay_file(&format!("{}/{}", MOUNT_POINT_PATH, CONFIG_OVERLAY_FILE));
 
     output_line("[7/9]: Setting up logging");
-    if DEBUG_MODE {
-        redirect_output_to_log_file()?;
+    if config.debug_mode {
+        profile.step("logging", || redirect_output_to_log_file(&config))?;
     }
 
     output_line("[8/9]: Launching web browser");
-    start_browser()?;
 
-    output_line("[9/9]: Starting interactive shell");
-    interactive_shell()?;
+    // Move the interactive shell to a forked child so the browser supervisor can
+    // own the foreground, mirroring how a service manager keeps its main unit
+    // alive while auxiliary tasks run alongside it.
+    profile.step("interactive-shell", || -> io::Result<()> {
+        match unsafe { libc::fork() } {
+            0 => {
+                output_line("[9/9]: Starting interactive shell");
+                let _ = interactive_shell(&config);
+                exit(0);
+            }
+            -1 => {
+                output_line("Failed to fork interactive shell");
+                Err(io::Error::last_os_error())
+            }
+            _process_id => {
+                output_line("[9/9]: Interactive shell started in background");
+                Ok(())
+            }
+        }
+    })?;
 
-    output_line("Initialization sequence completed successfully");
-    Ok(())
+    // Supervise the browser in the foreground; this never returns. The boot
+    // profile is flushed on the first successful launch.
+    supervise_browser(&config, profile)
 }
 
 /// Main entry point
@@ -466,3 +1405,81 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_shell_command;
+
+    #[test]
+    fn splits_a_plain_command() {
+        assert_eq!(
+            parse_shell_command("mount -t proc none /proc"),
+            vec!["mount", "-t", "proc", "none", "/proc"]
+        );
+    }
+
+    #[test]
+    fn empty_input_yields_no_arguments() {
+        assert!(parse_shell_command("   ").is_empty());
+        assert!(parse_shell_command("# just a comment").is_empty());
+    }
+
+    #[test]
+    fn double_quotes_preserve_spaces_within_a_word() {
+        assert_eq!(
+            parse_shell_command("echo \"hello world\""),
+            vec!["echo", "hello world"]
+        );
+    }
+
+    #[test]
+    fn quotes_join_adjacent_text_into_one_word() {
+        assert_eq!(parse_shell_command("mode=\"0777\"done"), vec!["mode=0777done"]);
+    }
+
+    #[test]
+    fn single_quotes_are_literal() {
+        assert_eq!(
+            parse_shell_command("echo '$PATH \"x\"'"),
+            vec!["echo", "$PATH \"x\""]
+        );
+    }
+
+    #[test]
+    fn backslash_escapes_the_next_character() {
+        assert_eq!(parse_shell_command("echo a\\ b"), vec!["echo", "a b"]);
+        assert_eq!(
+            parse_shell_command("echo \\\"quoted\\\""),
+            vec!["echo", "\"quoted\""]
+        );
+    }
+
+    #[test]
+    fn trailing_comment_is_stripped() {
+        assert_eq!(
+            parse_shell_command("mount /dev/sda1 /mnt # persistent log disk"),
+            vec!["mount", "/dev/sda1", "/mnt"]
+        );
+    }
+
+    #[test]
+    fn hash_inside_a_word_is_literal() {
+        assert_eq!(parse_shell_command("color=#ffffff"), vec!["color=#ffffff"]);
+    }
+
+    #[test]
+    fn mount_option_with_equals_and_path_separators() {
+        assert_eq!(
+            parse_shell_command("mount -t tmpfs -o uid=1000,gid=1000,mode=0777 tmpfs /tmp"),
+            vec![
+                "mount",
+                "-t",
+                "tmpfs",
+                "-o",
+                "uid=1000,gid=1000,mode=0777",
+                "tmpfs",
+                "/tmp"
+            ]
+        );
+    }
+}